@@ -14,16 +14,30 @@
 
 //! Helper functions and macros
 
+use std::sync::OnceLock;
+
+use jni::objects::{GlobalRef, JClass, JThrowable};
 use jni::sys::{jboolean, jbyte};
+use jni::JNIEnv;
 use log::error;
 use uwb_core::error::{Error, Result};
 use uwb_uci_packets::StatusCode;
 
+use crate::metrics;
+
+/// Fully qualified class name of the Java exception thrown on UCI failures.
+const UWB_EXCEPTION_CLASS: &str = "com/android/server/uwb/UwbException";
+
+/// Cached global ref to the `UwbException` class, populated on first throw so later throws don't
+/// pay for a fresh `FindClass` JNI round trip.
+static UWB_EXCEPTION_CLASS_REF: OnceLock<GlobalRef> = OnceLock::new();
+
 pub(crate) fn boolean_result_helper<T>(result: Result<T>, error_msg: &str) -> jboolean {
     match result {
         Ok(_) => true,
         Err(e) => {
             error!("{} failed with {:?}", error_msg, &e);
+            metrics::record_error(&e);
             false
         }
     }
@@ -39,16 +53,12 @@ pub(crate) fn byte_result_helper<T>(result: Result<T>, error_msg: &str) -> jbyte
 fn result_to_status_code<T>(result: Result<T>, error_msg: &str) -> StatusCode {
     let result = result.map_err(|e| {
         error!("{} failed with {:?}", error_msg, &e);
+        metrics::record_error(&e);
         e
     });
     match result {
         Ok(_) => StatusCode::UciStatusOk,
-        Err(Error::BadParameters) => StatusCode::UciStatusInvalidParam,
-        Err(Error::MaxSessionsExceeded) => StatusCode::UciStatusMaxSessionsExceeded,
-        Err(Error::CommandRetry) => StatusCode::UciStatusCommandRetry,
-        Err(Error::RegulationUwbOff) => StatusCode::UciStatusRegulationUwbOff,
-        // For other Error, only generic fail can be given.
-        Err(_) => StatusCode::UciStatusFailed,
+        Err(e) => e.into(),
     }
 }
 
@@ -56,11 +66,77 @@ pub(crate) fn option_result_helper<T>(result: Result<T>, error_msg: &str) -> Opt
     result
         .map_err(|e| {
             error!("{} failed with {:?}", error_msg, &e);
+            metrics::record_error(&e);
             e
         })
         .ok()
 }
 
+/// helper function to convert Result to Option, throwing a UwbException carrying the status
+/// code and error detail on Err
+pub(crate) fn throwing_result_helper<T>(
+    env: &mut JNIEnv,
+    result: Result<T>,
+    error_msg: &str,
+) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(e) => {
+            error!("{} failed with {:?}", error_msg, &e);
+            metrics::record_error(&e);
+            throw_uwb_exception(env, &e);
+            None
+        }
+    }
+}
+
+/// Constructs and throws a `UwbException` for the given error, falling back to logging if the
+/// throw itself fails (e.g. the class can't be found). If a Java exception is already pending,
+/// nothing is thrown.
+fn throw_uwb_exception(env: &mut JNIEnv, e: &Error) {
+    // Avoid double-throwing: if a Java exception is already pending, leave it alone.
+    match env.exception_check() {
+        Ok(true) => return,
+        Ok(false) => {}
+        Err(e) => {
+            error!("Failed to check for pending exception: {:?}", e);
+            return;
+        }
+    }
+    let status_code = StatusCode::from(e.clone());
+    let debug_msg = format!("{:?}", e);
+    let result: jni::errors::Result<()> = (|| {
+        let class = uwb_exception_class(env)?;
+        let msg = env.new_string(&debug_msg)?;
+        let exception = env.new_object(
+            class,
+            "(ILjava/lang/String;)V",
+            &[(u8::from(status_code) as jni::sys::jint).into(), (&msg).into()],
+        )?;
+        env.throw(JThrowable::from(exception))
+    })();
+    if let Err(e) = result {
+        error!("Failed to throw UwbException for {}: {:?}", debug_msg, e);
+    }
+}
+
+/// Returns a local `JClass` ref to the `UwbException` class, looking it up once and caching a
+/// `GlobalRef` to it for subsequent calls.
+fn uwb_exception_class<'local>(env: &mut JNIEnv<'local>) -> jni::errors::Result<JClass<'local>> {
+    let global_ref = match UWB_EXCEPTION_CLASS_REF.get() {
+        Some(global_ref) => global_ref.clone(),
+        None => {
+            let class = env.find_class(UWB_EXCEPTION_CLASS)?;
+            let global_ref = env.new_global_ref(class)?;
+            // Another thread may have raced us to populate the cache; either way, our own copy
+            // of the GlobalRef is valid to use below.
+            let _ = UWB_EXCEPTION_CLASS_REF.set(global_ref.clone());
+            global_ref
+        }
+    };
+    Ok(JClass::from(env.new_local_ref(&global_ref)?))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,6 +182,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_byte_result_helper_forwards_vendor_status_code() {
+        // Vendor-range (0xE0-0xFF) and reserved status codes should be forwarded verbatim rather
+        // than flattened to UciStatusFailed.
+        let vendor_code = StatusCode::try_from(0xF0u8).expect("vendor status code should parse");
+        assert_eq!(
+            byte_result_helper::<i8>(Err(Error::StatusCode(vendor_code)), "Test"),
+            0xF0u8 as i8
+        );
+    }
+
     #[test]
     fn test_option_result_helper() {
         let result: Result<i32> = Ok(42);
@@ -0,0 +1,120 @@
+// Copyright 2022, The Android Open Source Project
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-error-variant failure counters, incremented from `helper.rs` and polled by the framework.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use jni::objects::JClass;
+use jni::sys::jlongArray;
+use jni::JNIEnv;
+use uwb_core::error::Error;
+
+/// Stable discriminant for each `Error` variant tracked by the metrics counters below.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ErrorKind {
+    BadParameters = 0,
+    MaxSessionsExceeded = 1,
+    CommandRetry = 2,
+    RegulationUwbOff = 3,
+    DuplicatedSessionId = 4,
+    Timeout = 5,
+    /// A vendor-range or reserved UCI status code forwarded verbatim via `Error::StatusCode`.
+    VendorStatusCode = 6,
+    Unknown = 7,
+}
+
+const NUM_ERROR_KINDS: usize = 8;
+const ALL_ERROR_KINDS: [ErrorKind; NUM_ERROR_KINDS] = [
+    ErrorKind::BadParameters,
+    ErrorKind::MaxSessionsExceeded,
+    ErrorKind::CommandRetry,
+    ErrorKind::RegulationUwbOff,
+    ErrorKind::DuplicatedSessionId,
+    ErrorKind::Timeout,
+    ErrorKind::VendorStatusCode,
+    ErrorKind::Unknown,
+];
+
+impl From<&Error> for ErrorKind {
+    fn from(e: &Error) -> Self {
+        match e {
+            Error::BadParameters => ErrorKind::BadParameters,
+            Error::MaxSessionsExceeded => ErrorKind::MaxSessionsExceeded,
+            Error::CommandRetry => ErrorKind::CommandRetry,
+            Error::RegulationUwbOff => ErrorKind::RegulationUwbOff,
+            Error::DuplicatedSessionId => ErrorKind::DuplicatedSessionId,
+            Error::Timeout => ErrorKind::Timeout,
+            Error::StatusCode(_) => ErrorKind::VendorStatusCode,
+            Error::Unknown => ErrorKind::Unknown,
+        }
+    }
+}
+
+const ZERO_COUNTER: AtomicU64 = AtomicU64::new(0);
+static ERROR_COUNTERS: [AtomicU64; NUM_ERROR_KINDS] = [ZERO_COUNTER; NUM_ERROR_KINDS];
+
+/// Increments the counter for the `ErrorKind` corresponding to `e`.
+pub(crate) fn record_error(e: &Error) {
+    let kind = ErrorKind::from(e);
+    ERROR_COUNTERS[kind as usize].fetch_add(1, Ordering::Relaxed);
+}
+
+/// Returns a snapshot of every error counter, in `ErrorKind` discriminant order.
+pub(crate) fn snapshot_error_metrics() -> Vec<(ErrorKind, u64)> {
+    ALL_ERROR_KINDS
+        .iter()
+        .map(|&kind| (kind, ERROR_COUNTERS[kind as usize].load(Ordering::Relaxed)))
+        .collect()
+}
+
+/// JNI-exported entry point the framework polls for error telemetry. Returns a flat array of
+/// `[kind_0, count_0, kind_1, count_1, ...]` in `ErrorKind` discriminant order.
+#[no_mangle]
+pub extern "system" fn Java_com_android_server_uwb_jni_NativeUwbManager_nativeGetErrorMetrics(
+    mut env: JNIEnv,
+    _cls: JClass,
+) -> jlongArray {
+    let snapshot = snapshot_error_metrics();
+    let flattened: Vec<i64> =
+        snapshot.iter().flat_map(|(kind, count)| [*kind as i64, *count as i64]).collect();
+    match env.new_long_array(flattened.len() as i32) {
+        Ok(array) => {
+            if let Err(e) = env.set_long_array_region(&array, 0, &flattened) {
+                log::error!("Failed to populate error metrics array: {:?}", e);
+            }
+            array.into_raw()
+        }
+        Err(e) => {
+            log::error!("Failed to allocate error metrics array: {:?}", e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_error_increments_matching_kind() {
+        let before = snapshot_error_metrics();
+        record_error(&Error::Timeout);
+        let after = snapshot_error_metrics();
+        for ((kind, before_count), (_, after_count)) in before.iter().zip(after.iter()) {
+            let expected_delta = if *kind == ErrorKind::Timeout { 1 } else { 0 };
+            assert_eq!(after_count - before_count, expected_delta);
+        }
+    }
+}
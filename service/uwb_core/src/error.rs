@@ -0,0 +1,148 @@
+// Copyright 2022, The Android Open Source Project
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Error and Result types shared across uwb_core.
+
+use std::convert::TryFrom;
+
+use uwb_uci_packets::StatusCode;
+
+/// Errors that can occur in uwb_core.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// Bad parameters were supplied to an API.
+    BadParameters,
+    /// The maximum number of concurrent sessions has been reached.
+    MaxSessionsExceeded,
+    /// The command should be retried.
+    CommandRetry,
+    /// UWB is disabled by regulation policy.
+    RegulationUwbOff,
+    /// A session with the given ID already exists.
+    DuplicatedSessionId,
+    /// The operation timed out.
+    Timeout,
+    /// A UCI status code, including vendor-range (0xE0-0xFF) and reserved codes, that has no
+    /// other `Error` variant representing it. Carries the original code so it reaches the
+    /// framework unchanged instead of collapsing to a generic failure.
+    StatusCode(StatusCode),
+    /// An error occurred for which no more specific variant applies.
+    Unknown,
+}
+
+/// Result type used throughout uwb_core.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Converting an `Error` to a `StatusCode` is total: every variant maps to a UCI status, even
+/// when several variants collapse onto the same generic failure code.
+impl From<Error> for StatusCode {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::BadParameters => StatusCode::UciStatusInvalidParam,
+            Error::MaxSessionsExceeded => StatusCode::UciStatusMaxSessionsExceeded,
+            Error::CommandRetry => StatusCode::UciStatusCommandRetry,
+            Error::RegulationUwbOff => StatusCode::UciStatusRegulationUwbOff,
+            Error::DuplicatedSessionId => StatusCode::UciStatusFailed,
+            Error::Timeout => StatusCode::UciStatusFailed,
+            // Forward the original status code verbatim rather than remapping it, so vendor and
+            // reserved codes reach the framework intact.
+            Error::StatusCode(status_code) => status_code,
+            Error::Unknown => StatusCode::UciStatusFailed,
+        }
+    }
+}
+
+/// The reverse direction is partial: `UciStatusOk` isn't an error, so it has no `Error`
+/// counterpart.
+impl TryFrom<StatusCode> for Error {
+    type Error = ();
+
+    fn try_from(status_code: StatusCode) -> std::result::Result<Self, Self::Error> {
+        match status_code {
+            StatusCode::UciStatusOk => Err(()),
+            StatusCode::UciStatusInvalidParam => Ok(Error::BadParameters),
+            StatusCode::UciStatusMaxSessionsExceeded => Ok(Error::MaxSessionsExceeded),
+            StatusCode::UciStatusCommandRetry => Ok(Error::CommandRetry),
+            StatusCode::UciStatusRegulationUwbOff => Ok(Error::RegulationUwbOff),
+            // Every other named UCI status code is listed explicitly, as a best-effort enumeration
+            // (StatusCode isn't matched exhaustively here, since the trailing arm below still has
+            // to catch the open vendor/reserved byte range). None of them currently has a
+            // dedicated Error variant of its own, so they all surface as a generic failure.
+            StatusCode::UciStatusRejected
+            | StatusCode::UciStatusFailed
+            | StatusCode::UciStatusSyntaxError
+            | StatusCode::UciStatusInvalidRange
+            | StatusCode::UciStatusInvalidMessageSize
+            | StatusCode::UciStatusUnknownGid
+            | StatusCode::UciStatusUnknownOid
+            | StatusCode::UciStatusReadyState
+            | StatusCode::UciStatusBusy
+            | StatusCode::UciStatusErrorSessionNotExist
+            | StatusCode::UciStatusErrorSessionDuplicate
+            | StatusCode::UciStatusErrorSessionActive
+            | StatusCode::UciStatusErrorSessionNotConfigured
+            | StatusCode::UciStatusErrorActiveSessionsOngoing
+            | StatusCode::UciStatusErrorMulticastListFull
+            | StatusCode::UciStatusErrorAddressNotFound
+            | StatusCode::UciStatusErrorAddressAlreadyPresent
+            | StatusCode::UciStatusRangingTxFailed
+            | StatusCode::UciStatusRangingRxTimeout
+            | StatusCode::UciStatusRangingRxPhyDecFailed
+            | StatusCode::UciStatusRangingRxPhyToaFailed
+            | StatusCode::UciStatusRangingRxPhyStsFailed
+            | StatusCode::UciStatusRangingRxMacDecFailed
+            | StatusCode::UciStatusRangingRxMacIeDecFailed
+            | StatusCode::UciStatusRangingRxMacIeMissing => Ok(Error::Unknown),
+            // The only remaining bytes are the vendor range (0xE0-0xFF) and any value reserved
+            // by the spec but not yet given a name; those are carried through verbatim rather
+            // than collapsed to a generic failure.
+            status_code => Ok(Error::StatusCode(status_code)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Variants that map onto a UCI status code with no other `Error` variant sharing it, so the
+    /// round trip through `StatusCode` is guaranteed to return the original variant.
+    const ROUND_TRIP_VARIANTS: [Error; 4] = [
+        Error::BadParameters,
+        Error::MaxSessionsExceeded,
+        Error::CommandRetry,
+        Error::RegulationUwbOff,
+    ];
+
+    #[test]
+    fn test_error_status_code_round_trip() {
+        for error in ROUND_TRIP_VARIANTS {
+            let status_code = StatusCode::from(error.clone());
+            assert_eq!(Error::try_from(status_code), Ok(error));
+        }
+    }
+
+    #[test]
+    fn test_status_code_ok_has_no_error() {
+        assert_eq!(Error::try_from(StatusCode::UciStatusOk), Err(()));
+    }
+
+    #[test]
+    fn test_vendor_status_code_round_trip() {
+        let vendor_code = StatusCode::try_from(0xF0u8).expect("vendor status code should parse");
+        let error = Error::StatusCode(vendor_code);
+        assert_eq!(StatusCode::from(error.clone()), vendor_code);
+        assert_eq!(Error::try_from(vendor_code), Ok(error));
+    }
+}